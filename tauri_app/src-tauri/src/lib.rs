@@ -1,10 +1,14 @@
+mod protocol;
+mod registry;
 mod sidecar;
-use sidecar::SidecarManager;
+use registry::SidecarRegistry;
+use sidecar::{SidecarConfig, SidecarLogLine, SidecarStatus};
 
 use objc2_app_kit::{NSColor, NSWindow};
 use objc2::ffi::nil;
 use objc2::runtime::AnyObject;
 use window_vibrancy::{apply_vibrancy, NSVisualEffectMaterial};
+use std::collections::HashMap;
 
 #[cfg(target_os = "macos")]
 use objc2_app_kit::{NSWorkspace, NSBitmapImageRep};
@@ -24,10 +28,14 @@ use std::ffi::CStr;
 use base64::engine::general_purpose;
 #[cfg(target_os = "macos")]
 use base64::Engine;
+#[cfg(target_os = "macos")]
+use std::sync::{Mutex, OnceLock};
+#[cfg(target_os = "macos")]
+use std::time::SystemTime;
 
-// use tauri::menu::{Menu, MenuItem};
-// use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
-use tauri::{Emitter, Manager, State};
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{Emitter, Listener, Manager, State};
 use std::sync::Arc;
 use std::env;
 
@@ -113,18 +121,104 @@ async fn get_app_list() -> Result<Vec<AppMetadata>, String> {
 #[cfg(target_os = "macos")]
 #[tauri::command]
 async fn get_app_icon(bundle_id: String) -> Result<String, String> {
+    unsafe { icon_for_bundle_id(&bundle_id) }
+}
+
+#[cfg(not(target_os = "macos"))]
+#[derive(serde::Serialize, Clone)]
+struct AppMetadata {
+    name: String,
+    bundle_id: String,
+    path: String,
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+async fn get_app_list() -> Result<Vec<AppMetadata>, String> {
+    Ok(vec![])
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+async fn get_app_icon(_bundle_id: String) -> Result<String, String> {
+    Err("Not supported on this platform".to_string())
+}
+
+/// Reads name/bundle id/path off an `NSRunningApplication*`, shared by the
+/// activation-notification handlers and `get_frontmost_app`. (`get_app_list` reads
+/// the same fields inline.)
+#[cfg(target_os = "macos")]
+unsafe fn extract_app_metadata(app_ptr: *mut AnyObject) -> Option<AppMetadata> {
+    if app_ptr == nil {
+        return None;
+    }
+
+    let name_ns: *mut AnyObject = msg_send![app_ptr, localizedName];
+    if name_ns == nil {
+        return None;
+    }
+    let utf8_ptr: *const std::os::raw::c_char = msg_send![name_ns, UTF8String];
+    let name = CStr::from_ptr(utf8_ptr).to_string_lossy().into_owned();
+    if name.is_empty() {
+        return None;
+    }
+
+    let bundle_id_ns: *mut AnyObject = msg_send![app_ptr, bundleIdentifier];
+    let bundle_id = if bundle_id_ns != nil {
+        let utf8_ptr: *const std::os::raw::c_char = msg_send![bundle_id_ns, UTF8String];
+        CStr::from_ptr(utf8_ptr).to_string_lossy().into_owned()
+    } else {
+        let pid: i32 = msg_send![app_ptr, processIdentifier];
+        format!("pid_{}", pid)
+    };
+
+    let bundle_url: *mut AnyObject = msg_send![app_ptr, bundleURL];
+    let path = if bundle_url != nil {
+        let path_ns: *mut AnyObject = msg_send![bundle_url, path];
+        if path_ns != nil {
+            let utf8_ptr: *const std::os::raw::c_char = msg_send![path_ns, UTF8String];
+            CStr::from_ptr(utf8_ptr).to_string_lossy().into_owned()
+        } else {
+            String::new()
+        }
+    } else {
+        String::new()
+    };
+
+    Some(AppMetadata { name, bundle_id, path })
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+async fn get_frontmost_app() -> Result<AppMetadata, String> {
+    unsafe {
+        let workspace = NSWorkspace::sharedWorkspace();
+        let frontmost: *mut AnyObject = msg_send![&*workspace, frontmostApplication];
+        extract_app_metadata(frontmost).ok_or_else(|| "No frontmost application".to_string())
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+async fn get_frontmost_app() -> Result<AppMetadata, String> {
+    Err("Not supported on this platform".to_string())
+}
+
+/// Brings a running app to the foreground by bundle id, turning `get_app_list` into
+/// something the assistant can act on rather than just observe.
+#[cfg(target_os = "macos")]
+#[tauri::command]
+async fn activate_app(bundle_id: String) -> Result<(), String> {
     unsafe {
         let workspace = NSWorkspace::sharedWorkspace();
         let apps = workspace.runningApplications();
 
         for app in apps.iter() {
-            // Get bundle identifier for comparison
             let app_bundle_id_ns: *mut AnyObject = msg_send![&*app, bundleIdentifier];
             let app_bundle_id = if app_bundle_id_ns != nil {
                 let utf8_ptr: *const std::os::raw::c_char = msg_send![app_bundle_id_ns, UTF8String];
                 CStr::from_ptr(utf8_ptr).to_string_lossy().into_owned()
             } else {
-                // Fallback to process identifier
                 let pid: i32 = msg_send![&*app, processIdentifier];
                 format!("pid_{}", pid)
             };
@@ -133,123 +227,539 @@ async fn get_app_icon(bundle_id: String) -> Result<String, String> {
                 continue;
             }
 
-            // Get app icon
-            let icon: *mut AnyObject = msg_send![&*app, icon];
-            if icon == nil {
-                return Err("No icon available".to_string());
+            // NSApplicationActivateIgnoringOtherApps
+            let activated: bool = msg_send![&*app, activateWithOptions: 2u64];
+            return if activated {
+                Ok(())
+            } else {
+                Err(format!("Failed to activate '{}'", bundle_id))
+            };
+        }
+
+        Err(format!("No running app with bundle id '{}'", bundle_id))
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+async fn activate_app(_bundle_id: String) -> Result<(), String> {
+    Err("Not supported on this platform".to_string())
+}
+
+/// Resolves `bundle_id` to an installed app's URL via `NSWorkspace` and launches it.
+/// Unlike `activate_app`, this also works when the app isn't running yet.
+#[cfg(target_os = "macos")]
+#[tauri::command]
+async fn launch_app(bundle_id: String) -> Result<(), String> {
+    let (app_url, workspace) = unsafe {
+        let workspace = NSWorkspace::sharedWorkspace();
+        let bundle_id_ns = objc2_foundation::NSString::from_str(&bundle_id);
+        let app_url: *mut AnyObject =
+            msg_send![&*workspace, URLForApplicationWithBundleIdentifier: &*bundle_id_ns];
+        if app_url == nil {
+            return Err(format!("No installed app with bundle id '{}'", bundle_id));
+        }
+        (app_url, workspace)
+    };
+
+    let (tx, rx) = tokio::sync::oneshot::channel::<Result<(), String>>();
+    let tx = std::sync::Mutex::new(Some(tx));
+
+    unsafe {
+        let config_class = objc2::class!(NSWorkspaceOpenConfiguration);
+        let config: *mut AnyObject = msg_send![config_class, new];
+
+        let completion = StackBlock::new(move |running_app: *mut AnyObject, error: *mut AnyObject| {
+            let _ = running_app;
+            let result = if error == nil {
+                Ok(())
+            } else {
+                let desc: *mut AnyObject = msg_send![error, localizedDescription];
+                let message = if desc != nil {
+                    let utf8_ptr: *const std::os::raw::c_char = msg_send![desc, UTF8String];
+                    CStr::from_ptr(utf8_ptr).to_string_lossy().into_owned()
+                } else {
+                    "launch failed".to_string()
+                };
+                Err(message)
+            };
+            if let Some(tx) = tx.lock().unwrap().take() {
+                let _ = tx.send(result);
+            }
+        });
+
+        let _: () = msg_send![
+            &*workspace,
+            openApplicationAtURL: app_url,
+            configuration: config,
+            completionHandler: &completion,
+        ];
+    }
+
+    rx.await.map_err(|_| "Launch completion handler dropped".to_string())?
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+async fn launch_app(_bundle_id: String) -> Result<(), String> {
+    Err("Not supported on this platform".to_string())
+}
+
+/// A `get_app_icon`/`get_app_icons` result cached against the bundle's on-disk
+/// modified time, so a reinstalled or updated app picks up its new icon instead of
+/// serving a stale PNG indefinitely.
+#[cfg(target_os = "macos")]
+struct CachedIcon {
+    png_base64: String,
+    bundle_mtime: Option<SystemTime>,
+}
+
+#[cfg(target_os = "macos")]
+fn icon_cache() -> &'static Mutex<HashMap<String, CachedIcon>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, CachedIcon>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+#[cfg(target_os = "macos")]
+fn bundle_mtime(path: &str) -> Option<SystemTime> {
+    if path.is_empty() {
+        return None;
+    }
+    std::fs::metadata(path).ok()?.modified().ok()
+}
+
+/// Standard locations macOS installs `.app` bundles into, scanned (non-recursively)
+/// by `get_installed_apps` and the `get_app_icon` installed-app fallback.
+#[cfg(target_os = "macos")]
+fn app_search_directories() -> Vec<std::path::PathBuf> {
+    let mut dirs = vec![
+        std::path::PathBuf::from("/Applications"),
+        std::path::PathBuf::from("/System/Applications"),
+    ];
+    if let Some(home) = std::env::var_os("HOME") {
+        dirs.push(std::path::PathBuf::from(home).join("Applications"));
+    }
+    dirs
+}
+
+/// Reads name/bundle id off an on-disk `.app` bundle via `NSBundle`. Unlike
+/// `extract_app_metadata`, this works for apps that aren't currently running.
+#[cfg(target_os = "macos")]
+unsafe fn metadata_for_bundle_path(path: &std::path::Path) -> Option<AppMetadata> {
+    let path_str = path.to_str()?;
+    let ns_path = objc2_foundation::NSString::from_str(path_str);
+    let bundle_class = objc2::class!(NSBundle);
+    let bundle: *mut AnyObject = msg_send![bundle_class, bundleWithPath: &*ns_path];
+    if bundle == nil {
+        return None;
+    }
+
+    let bundle_id_ns: *mut AnyObject = msg_send![bundle, bundleIdentifier];
+    if bundle_id_ns == nil {
+        return None;
+    }
+    let utf8_ptr: *const std::os::raw::c_char = msg_send![bundle_id_ns, UTF8String];
+    let bundle_id = CStr::from_ptr(utf8_ptr).to_string_lossy().into_owned();
+
+    let info_dict: *mut AnyObject = msg_send![bundle, infoDictionary];
+    let name_ns: *mut AnyObject = if info_dict != nil {
+        msg_send![info_dict, objectForKey: ns_string!("CFBundleName")]
+    } else {
+        nil
+    };
+    let name = if name_ns != nil {
+        let utf8_ptr: *const std::os::raw::c_char = msg_send![name_ns, UTF8String];
+        CStr::from_ptr(utf8_ptr).to_string_lossy().into_owned()
+    } else {
+        path.file_stem().map(|s| s.to_string_lossy().into_owned())?
+    };
+
+    Some(AppMetadata {
+        name,
+        bundle_id,
+        path: path_str.to_string(),
+    })
+}
+
+#[cfg(target_os = "macos")]
+unsafe fn find_installed_app_path(bundle_id: &str) -> Option<String> {
+    for dir in app_search_directories() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("app") {
+                continue;
             }
+            if let Some(metadata) = metadata_for_bundle_path(&path) {
+                if metadata.bundle_id == bundle_id {
+                    return Some(metadata.path);
+                }
+            }
+        }
+    }
+    None
+}
 
-            // Convert icon to PNG data
-            let tiff_data: *mut AnyObject = msg_send![icon, TIFFRepresentation];
-            if tiff_data == nil {
-                return Err("Failed to get TIFF representation".to_string());
+/// Enumerates installed (not necessarily running) applications under the standard
+/// `/Applications`-style directories, so the assistant can discover apps it hasn't
+/// seen launched yet.
+#[cfg(target_os = "macos")]
+#[tauri::command]
+async fn get_installed_apps() -> Result<Vec<AppMetadata>, String> {
+    let mut result = Vec::new();
+
+    for dir in app_search_directories() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("app") {
+                continue;
             }
 
-            // Create bitmap representation from TIFF data
-            let bitmap_rep: *mut AnyObject = msg_send![NSBitmapImageRep::class(), alloc];
-            let bitmap_rep: *mut AnyObject = msg_send![bitmap_rep, initWithData: tiff_data];
-            if bitmap_rep == nil {
-                return Err("Failed to create bitmap representation".to_string());
+            if let Some(metadata) = unsafe { metadata_for_bundle_path(&path) } {
+                result.push(metadata);
             }
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+async fn get_installed_apps() -> Result<Vec<AppMetadata>, String> {
+    Ok(vec![])
+}
+
+/// Resolves `bundle_id` to a base64-encoded PNG icon. Prefers a running instance's
+/// live icon, falling back to `iconForFile:` on the installed bundle's path so this
+/// also works for apps that aren't currently running. Results are memoized in
+/// `icon_cache`, invalidated when the bundle's on-disk mtime changes.
+#[cfg(target_os = "macos")]
+unsafe fn icon_for_bundle_id(bundle_id: &str) -> Result<String, String> {
+    let workspace = NSWorkspace::sharedWorkspace();
+    let apps = workspace.runningApplications();
+
+    let mut bundle_path = String::new();
+    let mut icon: *mut AnyObject = nil;
 
-            // Convert to PNG data (NSBitmapImageFileTypePNG = 4)
-            let png_data: *mut AnyObject = msg_send![bitmap_rep, representationUsingType: 4u64, properties: nil];
-            if png_data == nil {
-                return Err("Failed to convert to PNG".to_string());
+    for app in apps.iter() {
+        let app_bundle_id_ns: *mut AnyObject = msg_send![&*app, bundleIdentifier];
+        let app_bundle_id = if app_bundle_id_ns != nil {
+            let utf8_ptr: *const std::os::raw::c_char = msg_send![app_bundle_id_ns, UTF8String];
+            CStr::from_ptr(utf8_ptr).to_string_lossy().into_owned()
+        } else {
+            let pid: i32 = msg_send![&*app, processIdentifier];
+            format!("pid_{}", pid)
+        };
+
+        if app_bundle_id != bundle_id {
+            continue;
+        }
+
+        icon = msg_send![&*app, icon];
+
+        let bundle_url: *mut AnyObject = msg_send![&*app, bundleURL];
+        if bundle_url != nil {
+            let path_ns: *mut AnyObject = msg_send![bundle_url, path];
+            if path_ns != nil {
+                let utf8_ptr: *const std::os::raw::c_char = msg_send![path_ns, UTF8String];
+                bundle_path = CStr::from_ptr(utf8_ptr).to_string_lossy().into_owned();
             }
+        }
+        break;
+    }
 
-            // Extract bytes and base64-encode
-            let bytes: *const u8 = msg_send![png_data, bytes];
-            let len: usize = msg_send![png_data, length];
-            let slice = std::slice::from_raw_parts(bytes, len);
-            let b64 = general_purpose::STANDARD.encode(slice);
+    if icon == nil && bundle_path.is_empty() {
+        bundle_path = find_installed_app_path(bundle_id)
+            .ok_or_else(|| format!("No app with bundle id '{}'", bundle_id))?;
+    }
 
-            return Ok(b64);
+    let mtime = bundle_mtime(&bundle_path);
+    if let Some(cached) = icon_cache().lock().unwrap().get(bundle_id) {
+        if cached.bundle_mtime == mtime {
+            return Ok(cached.png_base64.clone());
         }
+    }
 
-        Err("App not found".to_string())
+    if icon == nil {
+        let path_ns = objc2_foundation::NSString::from_str(&bundle_path);
+        icon = msg_send![&*workspace, iconForFile: &*path_ns];
     }
+    if icon == nil {
+        return Err("No icon available".to_string());
+    }
+
+    let tiff_data: *mut AnyObject = msg_send![icon, TIFFRepresentation];
+    if tiff_data == nil {
+        return Err("Failed to get TIFF representation".to_string());
+    }
+
+    let bitmap_rep: *mut AnyObject = msg_send![NSBitmapImageRep::class(), alloc];
+    let bitmap_rep: *mut AnyObject = msg_send![bitmap_rep, initWithData: tiff_data];
+    if bitmap_rep == nil {
+        return Err("Failed to create bitmap representation".to_string());
+    }
+
+    // NSBitmapImageFileTypePNG = 4
+    let png_data: *mut AnyObject = msg_send![bitmap_rep, representationUsingType: 4u64, properties: nil];
+    if png_data == nil {
+        return Err("Failed to convert to PNG".to_string());
+    }
+
+    let bytes: *const u8 = msg_send![png_data, bytes];
+    let len: usize = msg_send![png_data, length];
+    let slice = std::slice::from_raw_parts(bytes, len);
+    let png_base64 = general_purpose::STANDARD.encode(slice);
+
+    icon_cache().lock().unwrap().insert(
+        bundle_id.to_string(),
+        CachedIcon {
+            png_base64: png_base64.clone(),
+            bundle_mtime: mtime,
+        },
+    );
+
+    Ok(png_base64)
 }
 
-#[cfg(not(target_os = "macos"))]
-#[derive(serde::Serialize, Clone)]
-struct AppMetadata {
-    name: String,
-    bundle_id: String,
-    path: String,
+/// Batch form of `get_app_icon`, so the frontend can resolve icons for a whole app
+/// list in one round trip instead of one `invoke` per app. Bundle ids that fail to
+/// resolve are omitted from the result rather than failing the whole batch.
+#[cfg(target_os = "macos")]
+#[tauri::command]
+async fn get_app_icons(bundle_ids: Vec<String>) -> Result<HashMap<String, String>, String> {
+    let mut result = HashMap::with_capacity(bundle_ids.len());
+    for bundle_id in bundle_ids {
+        if let Ok(icon) = unsafe { icon_for_bundle_id(&bundle_id) } {
+            result.insert(bundle_id, icon);
+        }
+    }
+    Ok(result)
 }
 
 #[cfg(not(target_os = "macos"))]
 #[tauri::command]
-async fn get_app_list() -> Result<Vec<AppMetadata>, String> {
-    Ok(vec![])
+async fn get_app_icons(_bundle_ids: Vec<String>) -> Result<HashMap<String, String>, String> {
+    Ok(HashMap::new())
+}
+
+/// Sets the `NSWindow` collection behavior that lets the main window float above
+/// every macOS Space/virtual desktop (like a Spotlight-style panel) instead of being
+/// tied to whichever one it was created on.
+#[cfg(target_os = "macos")]
+fn apply_overlay_mode(window: &tauri::WebviewWindow, enabled: bool) -> Result<(), String> {
+    const NS_WINDOW_COLLECTION_BEHAVIOR_CAN_JOIN_ALL_SPACES: u64 = 1 << 0;
+    const NS_WINDOW_COLLECTION_BEHAVIOR_FULL_SCREEN_AUXILIARY: u64 = 1 << 8;
+
+    let ns_window = window.ns_window().map_err(|e| e.to_string())?;
+    unsafe {
+        let ns_window_ref = &*(ns_window as *const NSWindow);
+        let behavior: u64 = if enabled {
+            NS_WINDOW_COLLECTION_BEHAVIOR_CAN_JOIN_ALL_SPACES
+                | NS_WINDOW_COLLECTION_BEHAVIOR_FULL_SCREEN_AUXILIARY
+        } else {
+            0
+        };
+        let _: () = msg_send![ns_window_ref, setCollectionBehavior: behavior];
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn set_overlay_mode(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or_else(|| "Main window not found".to_string())?;
+    apply_overlay_mode(&window, enabled)
 }
 
 #[cfg(not(target_os = "macos"))]
 #[tauri::command]
-async fn get_app_icon(_bundle_id: String) -> Result<String, String> {
-    Err("Not supported on this platform".to_string())
+fn set_overlay_mode(_app: tauri::AppHandle, _enabled: bool) -> Result<(), String> {
+    Err("Overlay mode is only supported on macOS".to_string())
+}
+
+/// Registry key used by the legacy single-instance commands below, so existing
+/// frontend call sites keep working unchanged while still going through the
+/// multi-instance registry under the hood.
+fn default_sidecar_name() -> String {
+    env::var("SIDECAR_NAME").unwrap_or_else(|_| "mix".to_string())
+}
+
+/// Whether the default instance should auto-restart on an unexpected exit. Enabled
+/// unless explicitly turned off, so the backoff/crash-loop-capping supervisor in
+/// `sidecar.rs` is actually reachable instead of sitting dead behind `RestartPolicy`'s
+/// conservative (disabled) default.
+fn sidecar_auto_restart_enabled() -> bool {
+    env::var("SIDECAR_AUTO_RESTART")
+        .map(|v| v != "0" && v.to_lowercase() != "false")
+        .unwrap_or(true)
+}
+
+fn default_sidecar_config() -> Result<SidecarConfig, String> {
+    let config_dir = env::var("SIDECAR_CONFIG_DIR").unwrap_or_else(|_| ".".to_string());
+    let restart_policy = sidecar::RestartPolicy {
+        enabled: sidecar_auto_restart_enabled(),
+        ..sidecar::RestartPolicy::default()
+    };
+    SidecarConfig::new(default_sidecar_name(), config_dir)
+        .map(|config| config.with_restart_policy(restart_policy))
+        .map_err(|e| format!("Failed to allocate a port for the sidecar: {}", e))
 }
 
 #[tauri::command]
 async fn start_sidecar(
     app: tauri::AppHandle,
-    sidecar_manager: State<'_, Arc<SidecarManager>>,
+    registry: State<'_, Arc<SidecarRegistry>>,
 ) -> Result<(), String> {
-    sidecar_manager.inner().start_sidecar(&app).await
+    registry.inner().start(&app, default_sidecar_config()?).await
 }
 
 #[tauri::command]
 async fn stop_sidecar(
     app: tauri::AppHandle,
-    sidecar_manager: State<'_, Arc<SidecarManager>>,
+    registry: State<'_, Arc<SidecarRegistry>>,
 ) -> Result<(), String> {
-    sidecar_manager.inner().stop_sidecar(&app).await
+    registry.inner().stop(&app, &default_sidecar_name()).await
 }
 
 #[tauri::command]
-fn sidecar_status(sidecar_manager: State<'_, Arc<SidecarManager>>) -> bool {
-    sidecar_manager.inner().is_running()
+fn sidecar_status(registry: State<'_, Arc<SidecarRegistry>>) -> bool {
+    registry.inner().is_running(&default_sidecar_name())
 }
 
 #[tauri::command]
-async fn sidecar_health(sidecar_manager: State<'_, Arc<SidecarManager>>) -> Result<String, String> {
-    sidecar_manager.inner().health_check().await
+async fn sidecar_health(registry: State<'_, Arc<SidecarRegistry>>) -> Result<String, String> {
+    registry.inner().health(&default_sidecar_name()).await
 }
 
 #[tauri::command]
-fn sidecar_error(sidecar_manager: State<'_, Arc<SidecarManager>>) -> Option<String> {
-    sidecar_manager.inner().get_error()
+fn sidecar_error(registry: State<'_, Arc<SidecarRegistry>>) -> Option<String> {
+    registry.inner().error(&default_sidecar_name())
 }
 
 #[tauri::command]
 async fn send_prompt(
+    app: tauri::AppHandle,
     prompt: String,
-    sidecar_manager: State<'_, Arc<SidecarManager>>,
+    registry: State<'_, Arc<SidecarRegistry>>,
 ) -> Result<String, String> {
-    sidecar_manager.inner().send_prompt(&prompt).await
+    match registry.inner().get(&default_sidecar_name()) {
+        Some(manager) => manager.send_prompt(&app, &prompt).await,
+        None => Err("Sidecar is not running".to_string()),
+    }
+}
+
+#[tauri::command]
+async fn send_prompt_streaming(
+    app: tauri::AppHandle,
+    prompt: String,
+    request_id: String,
+    registry: State<'_, Arc<SidecarRegistry>>,
+) -> Result<(), String> {
+    match registry.inner().get(&default_sidecar_name()) {
+        Some(manager) => manager.send_prompt_streaming(&app, &prompt, &request_id).await,
+        None => Err("Sidecar is not running".to_string()),
+    }
+}
+
+#[tauri::command]
+fn cancel_prompt(request_id: String, registry: State<'_, Arc<SidecarRegistry>>) {
+    if let Some(manager) = registry.inner().get(&default_sidecar_name()) {
+        manager.cancel_prompt(&request_id);
+    }
+}
+
+#[tauri::command]
+fn sidecar_recent_logs(registry: State<'_, Arc<SidecarRegistry>>) -> Vec<SidecarLogLine> {
+    registry
+        .inner()
+        .get(&default_sidecar_name())
+        .map(|manager| manager.recent_logs())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+async fn write_sidecar_stdin(
+    data: String,
+    registry: State<'_, Arc<SidecarRegistry>>,
+) -> Result<(), String> {
+    match registry.inner().get(&default_sidecar_name()) {
+        Some(manager) => manager.write_stdin(data.as_bytes()).await,
+        None => Err("Sidecar is not running".to_string()),
+    }
+}
+
+/// Starts (or returns the already-running) sidecar instance named `name`, each
+/// instance getting its own OS-assigned port so several can run concurrently.
+#[tauri::command]
+async fn start_sidecar_instance(
+    app: tauri::AppHandle,
+    name: String,
+    config_dir: String,
+    registry: State<'_, Arc<SidecarRegistry>>,
+) -> Result<(), String> {
+    let config = SidecarConfig::new(name, config_dir)
+        .map_err(|e| format!("Failed to allocate a port for the sidecar: {}", e))?;
+    registry.inner().start(&app, config).await
+}
+
+#[tauri::command]
+async fn stop_sidecar_instance(
+    app: tauri::AppHandle,
+    name: String,
+    registry: State<'_, Arc<SidecarRegistry>>,
+) -> Result<(), String> {
+    registry.inner().stop(&app, &name).await
+}
+
+#[tauri::command]
+fn list_sidecar_instances(registry: State<'_, Arc<SidecarRegistry>>) -> Vec<String> {
+    registry.inner().names()
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let sidecar_manager = Arc::new(SidecarManager::new());
+    let sidecar_registry = Arc::new(SidecarRegistry::new());
 
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_macos_permissions::init())
-        .manage(sidecar_manager.clone())
+        .manage(sidecar_registry.clone())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
         .invoke_handler(tauri::generate_handler![
             get_app_list,
             get_app_icon,
+            get_app_icons,
+            get_installed_apps,
+            get_frontmost_app,
+            activate_app,
+            launch_app,
             start_sidecar,
             stop_sidecar,
             sidecar_status,
             sidecar_health,
             sidecar_error,
-            send_prompt
+            send_prompt,
+            send_prompt_streaming,
+            cancel_prompt,
+            sidecar_recent_logs,
+            write_sidecar_stdin,
+            start_sidecar_instance,
+            stop_sidecar_instance,
+            list_sidecar_instances,
+            set_overlay_mode
         ])
         .setup(move |app| {
             // Load environment variables from .env file
@@ -276,10 +786,87 @@ pub fn run() {
                     let ns_window_ref = &*(ns_window as *const NSWindow);
                     ns_window_ref.setBackgroundColor(Some(&bg_color));
                 }
+
+                // Pin the window across all Spaces so it behaves like a floating
+                // overlay panel rather than being tied to one virtual desktop.
+                if let Err(e) = apply_overlay_mode(&window, true) {
+                    eprintln!("Failed to enable overlay mode: {}", e);
+                }
             }
 
+            // Native application menu bar: App/Edit/View/Window with standard
+            // predefined items plus a couple of app-specific actions.
+            let toggle_sidecar_item = MenuItem::with_id(
+                app,
+                "toggle_sidecar",
+                "Toggle Sidecar",
+                true,
+                Some("CmdOrCtrl+Shift+T"),
+            )?;
+            let reload_item =
+                MenuItem::with_id(app, "reload", "Reload", true, Some("CmdOrCtrl+R"))?;
+
+            let app_menu = Submenu::with_items(
+                app,
+                "App",
+                true,
+                &[
+                    &PredefinedMenuItem::about(app, Some("About"), None)?,
+                    &PredefinedMenuItem::separator(app)?,
+                    &toggle_sidecar_item,
+                    &PredefinedMenuItem::separator(app)?,
+                    &PredefinedMenuItem::quit(app, Some("Quit"))?,
+                ],
+            )?;
+            let edit_menu = Submenu::with_items(
+                app,
+                "Edit",
+                true,
+                &[
+                    &PredefinedMenuItem::copy(app, Some("Copy"))?,
+                    &PredefinedMenuItem::paste(app, Some("Paste"))?,
+                ],
+            )?;
+            let view_menu = Submenu::with_items(app, "View", true, &[&reload_item])?;
+            let window_menu = Submenu::with_items(
+                app,
+                "Window",
+                true,
+                &[&PredefinedMenuItem::minimize(app, Some("Minimize"))?],
+            )?;
+
+            let app_menu_bar = Menu::with_items(
+                app,
+                &[&app_menu, &edit_menu, &view_menu, &window_menu],
+            )?;
+
+            // On macOS this replaces the auto-generated default menu; elsewhere it's
+            // attached directly to the window.
+            #[cfg(target_os = "macos")]
+            app.set_menu(app_menu_bar)?;
+            #[cfg(not(target_os = "macos"))]
+            window.set_menu(app_menu_bar)?;
+
+            app.on_menu_event(|app, event| match event.id().as_ref() {
+                "toggle_sidecar" => {
+                    // Same show/hide logic as the global Cmd/Ctrl+Shift+T shortcut.
+                    if let Some(window) = app.get_webview_window("main") {
+                        if window.is_visible().unwrap_or(false) {
+                            let _ = window.hide();
+                        } else {
+                            let _ = window.show();
+                            let _ = window.set_focus();
+                        }
+                    }
+                }
+                "reload" => {
+                    let _ = app.emit("menu://reload", ());
+                }
+                _ => {}
+            });
+
             // Clone for auto-start
-            let startup_manager = sidecar_manager.clone();
+            let startup_registry = sidecar_registry.clone();
             let startup_handle = app.handle().clone();
 
             // Check if sidecar should be auto-started (defaults to true for backward compatibility)
@@ -288,9 +875,16 @@ pub fn run() {
                 .to_lowercase() == "true";
 
             if sidecar_enabled {
-                // Auto-start sidecar on app launch
+                // Auto-start the default sidecar instance on app launch
                 tauri::async_runtime::spawn(async move {
-                    if let Err(e) = startup_manager.start_sidecar(&startup_handle).await {
+                    let config = match default_sidecar_config() {
+                        Ok(config) => config,
+                        Err(e) => {
+                            eprintln!("Failed to auto-start sidecar: {}", e);
+                            return;
+                        }
+                    };
+                    if let Err(e) = startup_registry.start(&startup_handle, config).await {
                         eprintln!("Failed to auto-start sidecar: {}", e);
                     }
                 });
@@ -300,76 +894,119 @@ pub fn run() {
 
 
             // Create system tray
-            // let quit_item = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-            // let show_item = MenuItem::with_id(app, "show", "Show", true, None::<&str>)?;
-            // let hide_item = MenuItem::with_id(app, "hide", "Hide", true, None::<&str>)?;
-            // // let sidecar_status_item =
-            // //     MenuItem::with_id(app, "sidecar_status", "Sidecar Status", true, None::<&str>)?;
-
-            // let tray_menu = Menu::with_items(
-            //     app,
-            //     &[&show_item, &hide_item, &quit_item],
-            // )?;
-
-            // let _tray = TrayIconBuilder::new()
-            //     .icon(app.default_window_icon().unwrap().clone())
-            //     .menu(&tray_menu)
-            //     .show_menu_on_left_click(false)
-            //     .on_menu_event(|app, event| match event.id.as_ref() {
-            //         "quit" => {
-            //             println!("Quit menu item clicked");
-            //             app.exit(0);
-            //         }
-            //         "show" => {
-            //             println!("Show menu item clicked");
-            //             if let Some(window) = app.get_webview_window("main") {
-            //                 let _ = window.show();
-            //                 let _ = window.set_focus();
-            //             }
-            //         }
-            //         "hide" => {
-            //             println!("Hide menu item clicked");
-            //             if let Some(window) = app.get_webview_window("main") {
-            //                 let _ = window.hide();
-            //             }
-            //         }
-            //         _ => {
-            //             println!("Unhandled menu item: {:?}", event.id);
-            //         }
-            //     })
-            //     .on_tray_icon_event(|tray, event| match event {
-            //         TrayIconEvent::Click {
-            //             button: MouseButton::Left,
-            //             button_state: MouseButtonState::Up,
-            //             ..
-            //         } => {
-            //             println!("Left click on tray icon");
-            //             let app = tray.app_handle();
-            //             if let Some(window) = app.get_webview_window("main") {
-            //                 if window.is_visible().unwrap_or(false) {
-            //                     let _ = window.hide();
-            //                 } else {
-            //                     let _ = window.show();
-            //                     let _ = window.set_focus();
-            //                 }
-            //             }
-            //         }
-            //         TrayIconEvent::DoubleClick {
-            //             button: MouseButton::Left,
-            //             ..
-            //         } => {
-            //             println!("Double click on tray icon");
-            //             let app = tray.app_handle();
-            //             if let Some(window) = app.get_webview_window("main") {
-            //                 let _ = window.show();
-            //                 let _ = window.set_focus();
-            //             }
-            //         }
-            //         _ => {
-            //             println!("Unhandled tray event: {:?}", event);
-            //         }
-            //     })
-            //     .build(app)?;
+            let quit_item = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+            let show_item = MenuItem::with_id(app, "show", "Show", true, None::<&str>)?;
+            let hide_item = MenuItem::with_id(app, "hide", "Hide", true, None::<&str>)?;
+            let restart_sidecar_item =
+                MenuItem::with_id(app, "restart_sidecar", "Restart sidecar", true, None::<&str>)?;
+            let sidecar_status_item =
+                MenuItem::with_id(app, "sidecar_status", "Sidecar: checking...", false, None::<&str>)?;
+
+            let tray_menu = Menu::with_items(
+                app,
+                &[
+                    &sidecar_status_item,
+                    &restart_sidecar_item,
+                    &show_item,
+                    &hide_item,
+                    &quit_item,
+                ],
+            )?;
+
+            let _tray = TrayIconBuilder::new()
+                .icon(app.default_window_icon().unwrap().clone())
+                .menu(&tray_menu)
+                .show_menu_on_left_click(false)
+                .on_menu_event(|app, event| match event.id.as_ref() {
+                    "quit" => {
+                        println!("Quit menu item clicked");
+                        app.exit(0);
+                    }
+                    "show" => {
+                        println!("Show menu item clicked");
+                        if let Some(window) = app.get_webview_window("main") {
+                            let _ = window.show();
+                            let _ = window.set_focus();
+                        }
+                    }
+                    "hide" => {
+                        println!("Hide menu item clicked");
+                        if let Some(window) = app.get_webview_window("main") {
+                            let _ = window.hide();
+                        }
+                    }
+                    "restart_sidecar" => {
+                        println!("Restart sidecar menu item clicked");
+                        let app_handle = app.clone();
+                        let registry = app_handle.state::<Arc<SidecarRegistry>>().inner().clone();
+                        tauri::async_runtime::spawn(async move {
+                            let _ = registry.stop(&app_handle, &default_sidecar_name()).await;
+                            match default_sidecar_config() {
+                                Ok(config) => {
+                                    if let Err(e) = registry.start(&app_handle, config).await {
+                                        eprintln!("Failed to restart sidecar from tray: {}", e);
+                                    }
+                                }
+                                Err(e) => eprintln!("Failed to restart sidecar from tray: {}", e),
+                            }
+                        });
+                    }
+                    _ => {
+                        println!("Unhandled menu item: {:?}", event.id);
+                    }
+                })
+                .on_tray_icon_event(|tray, event| match event {
+                    TrayIconEvent::Click {
+                        button: MouseButton::Left,
+                        button_state: MouseButtonState::Up,
+                        ..
+                    } => {
+                        println!("Left click on tray icon");
+                        let app = tray.app_handle();
+                        if let Some(window) = app.get_webview_window("main") {
+                            if window.is_visible().unwrap_or(false) {
+                                let _ = window.hide();
+                            } else {
+                                let _ = window.show();
+                                let _ = window.set_focus();
+                            }
+                        }
+                    }
+                    TrayIconEvent::DoubleClick {
+                        button: MouseButton::Left,
+                        ..
+                    } => {
+                        println!("Double click on tray icon");
+                        let app = tray.app_handle();
+                        if let Some(window) = app.get_webview_window("main") {
+                            let _ = window.show();
+                            let _ = window.set_focus();
+                        }
+                    }
+                    _ => {
+                        println!("Unhandled tray event: {:?}", event);
+                    }
+                })
+                .build(app)?;
+
+            // Keep the "Sidecar: ..." menu item in sync with the manager's own
+            // running/stopped/error state instead of polling it.
+            app.listen("sidecar://status", move |event| {
+                let Ok(status) = serde_json::from_str::<SidecarStatus>(event.payload()) else {
+                    return;
+                };
+                // Other registry instances (started via `start_sidecar_instance`) emit
+                // on this same event; only the default instance drives this tray label.
+                if status.name != default_sidecar_name() {
+                    return;
+                }
+                let label = match status.error {
+                    Some(error) => format!("Sidecar: error ({})", error),
+                    None if status.running => "Sidecar: running".to_string(),
+                    None => "Sidecar: stopped".to_string(),
+                };
+                let _ = sidecar_status_item.set_text(label);
+            });
 
             // Register global shortcut for window toggle
             #[cfg(desktop)]
@@ -425,19 +1062,54 @@ pub fn run() {
                             // Define notification names as NSString constants
                             let launch_notification = ns_string!("NSWorkspaceDidLaunchApplicationNotification");
                             let terminate_notification = ns_string!("NSWorkspaceDidTerminateApplicationNotification");
-                            
+                            let activate_notification = ns_string!("NSWorkspaceDidActivateApplicationNotification");
+                            let deactivate_notification = ns_string!("NSWorkspaceDidDeactivateApplicationNotification");
+                            let app_key = ns_string!("NSWorkspaceApplicationKey");
+
                             // Create observer for app launches
                             let launch_app_handle = app_handle.clone();
                             let launch_block = StackBlock::new(move |_notif: NonNull<objc2_foundation::NSNotification>| {
                                 let _ = launch_app_handle.emit("app-list-changed", ());
                             });
-                            
+
                             // Create observer for app terminations
                             let term_app_handle = app_handle.clone();
                             let term_block = StackBlock::new(move |_notif: NonNull<objc2_foundation::NSNotification>| {
                                 let _ = term_app_handle.emit("app-list-changed", ());
                             });
-                            
+
+                            // Create observer for app activation (the new frontmost app)
+                            let activate_app_handle = app_handle.clone();
+                            let activate_block = StackBlock::new(move |notif: NonNull<objc2_foundation::NSNotification>| {
+                                let notif_ref = notif.as_ref();
+                                let user_info: *mut AnyObject = msg_send![notif_ref, userInfo];
+                                if user_info == nil {
+                                    return;
+                                }
+                                let running_app: *mut AnyObject = msg_send![user_info, objectForKey: app_key];
+                                if let Some(metadata) = extract_app_metadata(running_app) {
+                                    let _ = activate_app_handle.emit("active-app-changed", metadata);
+                                }
+                            });
+
+                            // Create observer for app deactivation (the app losing focus). Emitted
+                            // on its own event rather than "active-app-changed" -- that event is
+                            // reserved for the app actually gaining focus, so a listener doesn't
+                            // have to guess which of two back-to-back payloads is the real
+                            // frontmost app.
+                            let deactivate_app_handle = app_handle.clone();
+                            let deactivate_block = StackBlock::new(move |notif: NonNull<objc2_foundation::NSNotification>| {
+                                let notif_ref = notif.as_ref();
+                                let user_info: *mut AnyObject = msg_send![notif_ref, userInfo];
+                                if user_info == nil {
+                                    return;
+                                }
+                                let running_app: *mut AnyObject = msg_send![user_info, objectForKey: app_key];
+                                if let Some(metadata) = extract_app_metadata(running_app) {
+                                    let _ = deactivate_app_handle.emit("app-deactivated", metadata);
+                                }
+                            });
+
                             // Register observers
                             let _launch_token = nc.addObserverForName_object_queue_usingBlock(
                                 Some(launch_notification),
@@ -445,14 +1117,28 @@ pub fn run() {
                                 None,  // current thread queue
                                 &launch_block,
                             );
-                            
+
                             let _term_token = nc.addObserverForName_object_queue_usingBlock(
                                 Some(terminate_notification),
                                 None,  // any sender
                                 None,  // current thread queue
                                 &term_block,
                             );
-                            
+
+                            let _activate_token = nc.addObserverForName_object_queue_usingBlock(
+                                Some(activate_notification),
+                                None,  // any sender
+                                None,  // current thread queue
+                                &activate_block,
+                            );
+
+                            let _deactivate_token = nc.addObserverForName_object_queue_usingBlock(
+                                Some(deactivate_notification),
+                                None,  // any sender
+                                None,  // current thread queue
+                                &deactivate_block,
+                            );
+
                             println!("NSWorkspace notification observers registered for real-time app changes");
                             
                             // Keep the thread alive to process notifications
@@ -0,0 +1,58 @@
+//! Typed request/response types for the sidecar's HTTP API, plus the error type
+//! returned by `SidecarManager`'s calls into it. Keeping these here (rather than
+//! poking at `serde_json::Value` ad hoc at each call site) means a new endpoint is a
+//! new pair of structs, not a new round of stringly-typed field lookups.
+
+use std::fmt;
+
+/// Base URL a sidecar bound to `port` is reachable on. Each `SidecarManager` instance
+/// owns its own port (see `SidecarConfig`), so this is computed per-call rather than
+/// hardcoded.
+pub fn base_url(port: u16) -> String {
+    format!("http://localhost:{}", port)
+}
+
+/// Failure modes for a call against the sidecar's HTTP API.
+#[derive(Debug)]
+pub enum SidecarError {
+    /// No child process is currently being tracked.
+    NotRunning,
+    /// The sidecar responded, but with a non-2xx status.
+    Http(reqwest::StatusCode),
+    /// The request never made it to (or back from) the sidecar.
+    Transport(reqwest::Error),
+    /// The response body didn't match the expected shape.
+    Decode(reqwest::Error),
+}
+
+impl fmt::Display for SidecarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SidecarError::NotRunning => write!(f, "sidecar is not running"),
+            SidecarError::Http(status) => write!(f, "sidecar returned status {}", status),
+            SidecarError::Transport(e) => write!(f, "sidecar request failed: {}", e),
+            SidecarError::Decode(e) => write!(f, "failed to decode sidecar response: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SidecarError {}
+
+impl From<SidecarError> for String {
+    fn from(err: SidecarError) -> Self {
+        err.to_string()
+    }
+}
+
+/// `GET /api/health` response.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct HealthResponse {
+    pub status: String,
+}
+
+/// `POST /api/prompt` request body.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PromptRequest<'a> {
+    pub prompt: &'a str,
+}
+
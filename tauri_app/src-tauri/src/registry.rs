@@ -0,0 +1,91 @@
+//! A keyed collection of independently managed sidecar instances, so the app can run
+//! more than one model backend at once (each on its own port) instead of being tied
+//! to a single global `SidecarManager`.
+
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use tauri::AppHandle;
+
+use crate::sidecar::{SidecarConfig, SidecarManager};
+
+#[derive(Debug, Default)]
+pub struct SidecarRegistry {
+    instances: DashMap<String, Arc<SidecarManager>>,
+}
+
+impl SidecarRegistry {
+    pub fn new() -> Self {
+        Self {
+            instances: DashMap::new(),
+        }
+    }
+
+    /// Returns the existing instance for `config.name`, or creates (but does not
+    /// start) one from `config`. Errors if an instance with this name already exists
+    /// under a different `config_dir`/`extra_args`, rather than silently keeping the
+    /// original instance's settings and discarding the caller's config.
+    fn get_or_create(&self, config: SidecarConfig) -> Result<Arc<SidecarManager>, String> {
+        if let Some(existing) = self.instances.get(&config.name) {
+            let current = existing.config();
+            if current.config_dir != config.config_dir || current.extra_args != config.extra_args {
+                return Err(format!(
+                    "Sidecar instance '{}' already exists with a different config (config_dir: '{}', extra_args: {:?}); stop it first to reconfigure",
+                    config.name, current.config_dir, current.extra_args
+                ));
+            }
+            return Ok(existing.clone());
+        }
+
+        Ok(self
+            .instances
+            .entry(config.name.clone())
+            .or_insert_with(|| {
+                let restart_policy = config.restart_policy.clone();
+                Arc::new(SidecarManager::new(config).with_restart_policy(restart_policy))
+            })
+            .clone())
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<SidecarManager>> {
+        self.instances.get(name).map(|entry| entry.clone())
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.instances.iter().map(|entry| entry.key().clone()).collect()
+    }
+
+    pub async fn start(&self, app: &AppHandle, config: SidecarConfig) -> Result<(), String> {
+        let manager = self.get_or_create(config)?;
+        manager.start_sidecar(app).await
+    }
+
+    /// Stops the instance and drops it from `instances` so a later `start` with a
+    /// different `config_dir`/`extra_args` recreates it instead of hitting
+    /// `get_or_create`'s conflicting-config error forever.
+    pub async fn stop(&self, app: &AppHandle, name: &str) -> Result<(), String> {
+        match self.get(name) {
+            Some(manager) => {
+                let result = manager.stop_sidecar(app).await;
+                self.instances.remove(name);
+                result
+            }
+            None => Ok(()), // Nothing to stop.
+        }
+    }
+
+    pub fn is_running(&self, name: &str) -> bool {
+        self.get(name).map(|m| m.is_running()).unwrap_or(false)
+    }
+
+    pub async fn health(&self, name: &str) -> Result<String, String> {
+        match self.get(name) {
+            Some(manager) => manager.health_check().await,
+            None => Err(format!("No sidecar instance named '{}'", name)),
+        }
+    }
+
+    pub fn error(&self, name: &str) -> Option<String> {
+        self.get(name).and_then(|m| m.get_error())
+    }
+}
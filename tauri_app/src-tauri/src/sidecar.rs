@@ -1,13 +1,186 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::{Arc, Mutex};
-use std::env;
-use tauri::AppHandle;
+use std::time::{SystemTime, UNIX_EPOCH};
+use futures_util::StreamExt;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
 use tauri_plugin_shell::{process::{CommandEvent, CommandChild}, ShellExt};
-use tokio::time::{sleep, Duration};
+use tokio::time::{sleep, Duration, Instant};
+
+use crate::protocol::{base_url, HealthResponse, PromptRequest, SidecarError};
+
+/// How often to poll the health endpoint while waiting for the sidecar to come up.
+const READINESS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Overall time budget for the sidecar to report healthy before we give up.
+const READINESS_TIMEOUT: Duration = Duration::from_secs(15);
+/// Number of recent log lines kept in memory for a newly opened window to fetch.
+const LOG_BUFFER_CAPACITY: usize = 1000;
+
+/// Generates the internal `request_id` used by `send_prompt`'s non-streaming wrapper.
+fn next_prompt_id() -> u32 {
+    static NEXT: AtomicU32 = AtomicU32::new(0);
+    NEXT.fetch_add(1, Ordering::SeqCst)
+}
+
+/// A single captured line of sidecar stdout/stderr, as forwarded on `"sidecar://log"`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SidecarLogLine {
+    pub stream: &'static str,
+    pub line: String,
+}
+
+/// Payload emitted on `"sidecar://exit"` when the monitored process goes away.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SidecarExit {
+    pub code: Option<i32>,
+    pub error: Option<String>,
+}
+
+/// Payload emitted on `"sidecar://status"` any time the running/stopped/error state
+/// changes, so UI like the tray menu can stay in sync without polling.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SidecarStatus {
+    pub name: String,
+    pub running: bool,
+    pub error: Option<String>,
+}
+
+/// Everything needed to spawn and address one sidecar instance. Each
+/// `SidecarManager` owns exactly one of these, which is what lets
+/// `SidecarRegistry` run several independent instances side by side, each on its own
+/// port.
+#[derive(Debug, Clone)]
+pub struct SidecarConfig {
+    /// Registry key and sidecar binary name (passed to `app.shell().sidecar(name)`).
+    pub name: String,
+    /// `-c` argument: the directory the Go server should use for its own config/state.
+    pub config_dir: String,
+    /// Port the Go server should bind its HTTP API to.
+    pub port: u16,
+    /// Additional CLI args appended after the standard ones.
+    pub extra_args: Vec<String>,
+    /// Auto-restart policy for the `SidecarManager` created from this config. Disabled
+    /// by default, same as `RestartPolicy::default()`; callers opt in with
+    /// `with_restart_policy`.
+    pub restart_policy: RestartPolicy,
+}
+
+impl SidecarConfig {
+    /// Builds a config for `name`, binding a free OS-assigned port.
+    pub fn new(name: impl Into<String>, config_dir: impl Into<String>) -> std::io::Result<Self> {
+        Ok(Self {
+            name: name.into(),
+            config_dir: config_dir.into(),
+            port: allocate_free_port()?,
+            extra_args: Vec::new(),
+            restart_policy: RestartPolicy::default(),
+        })
+    }
+
+    pub fn with_extra_args(mut self, extra_args: Vec<String>) -> Self {
+        self.extra_args = extra_args;
+        self
+    }
+
+    pub fn with_restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
+        self.restart_policy = restart_policy;
+        self
+    }
+}
+
+/// Probes an OS-assigned free TCP port by binding to port 0, reading back what the
+/// kernel gave us, then releasing it again so the sidecar process can bind it.
+fn allocate_free_port() -> std::io::Result<u16> {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    Ok(listener.local_addr()?.port())
+}
+
+/// Backoff/cap parameters for the auto-restart supervisor. Disabled (`enabled: false`)
+/// by default so existing callers keep today's "crash and stay dead" behavior unless
+/// they opt in.
+#[derive(Debug, Clone)]
+pub struct RestartPolicy {
+    pub enabled: bool,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub multiplier: f64,
+    /// How long the process has to stay healthy before the backoff counter resets.
+    pub stable_after: Duration,
+    /// Crash-loop guard: give up once this many restarts happen inside `window`.
+    pub max_restarts_per_window: u32,
+    pub window: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2.0,
+            stable_after: Duration::from_secs(30),
+            max_restarts_per_window: 5,
+            window: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Returns a pseudo-random float in `[0, 1)`, good enough for backoff jitter without
+/// pulling in a `rand` dependency.
+fn jitter_fraction() -> f64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    (nanos % 1_000) as f64 / 1_000.0
+}
+
+/// Append a captured output line to the ring buffer and forward it to the frontend
+/// over `"sidecar://log"`, dropping the oldest line once the buffer is full.
+fn push_log_line(
+    buffer: &Arc<Mutex<VecDeque<SidecarLogLine>>>,
+    app: &AppHandle,
+    stream: &'static str,
+    line: String,
+) {
+    let entry = SidecarLogLine { stream, line };
+
+    {
+        let mut buffer = buffer.lock().unwrap();
+        if buffer.len() >= LOG_BUFFER_CAPACITY {
+            buffer.pop_front();
+        }
+        buffer.push_back(entry.clone());
+    }
+
+    let _ = app.emit("sidecar://log", entry);
+}
+
+fn backoff_delay(policy: &RestartPolicy, attempt: u32) -> Duration {
+    let base_ms = policy.base_delay.as_millis() as f64;
+    let capped_ms = (base_ms * policy.multiplier.powi(attempt as i32))
+        .min(policy.max_delay.as_millis() as f64);
+    let jittered_ms = capped_ms + capped_ms * 0.2 * jitter_fraction();
+    Duration::from_millis(jittered_ms as u64)
+}
 
 #[derive(Debug)]
 pub struct SidecarManager {
+    config: SidecarConfig,
     child: Arc<Mutex<Option<CommandChild>>>,
     error_message: Arc<Mutex<Option<String>>>,
+    restart_policy: RestartPolicy,
+    /// Set while a deliberate `stop_sidecar` is in flight so the monitoring task can
+    /// tell an intentional kill apart from a crash and skip the auto-restart.
+    stopping: Arc<AtomicBool>,
+    restart_attempts: Arc<AtomicU32>,
+    restart_window: Arc<Mutex<(Instant, u32)>>,
+    log_buffer: Arc<Mutex<VecDeque<SidecarLogLine>>>,
+    /// Cancellation flags for in-flight `send_prompt_streaming` calls, keyed by
+    /// `request_id`, so `cancel_prompt` can stop one without touching the others.
+    active_prompts: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
 }
 
 impl Drop for SidecarManager {
@@ -22,92 +195,209 @@ impl Drop for SidecarManager {
 }
 
 impl SidecarManager {
-    pub fn new() -> Self {
+    pub fn new(config: SidecarConfig) -> Self {
         Self {
+            config,
             child: Arc::new(Mutex::new(None)),
             error_message: Arc::new(Mutex::new(None)),
+            restart_policy: RestartPolicy::default(),
+            stopping: Arc::new(AtomicBool::new(false)),
+            restart_attempts: Arc::new(AtomicU32::new(0)),
+            restart_window: Arc::new(Mutex::new((Instant::now(), 0))),
+            log_buffer: Arc::new(Mutex::new(VecDeque::with_capacity(LOG_BUFFER_CAPACITY))),
+            active_prompts: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
-    pub async fn start_sidecar(&self, app: &AppHandle) -> Result<(), String> {
+    /// Opt into automatic supervised restarts using the given backoff policy.
+    pub fn with_restart_policy(mut self, policy: RestartPolicy) -> Self {
+        self.restart_policy = policy;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    pub fn port(&self) -> u16 {
+        self.config.port
+    }
+
+    pub fn config(&self) -> &SidecarConfig {
+        &self.config
+    }
+
+    pub async fn start_sidecar(self: &Arc<Self>, app: &AppHandle) -> Result<(), String> {
         // Check if already running
         if self.child.lock().unwrap().is_some() {
             return Ok(());
         }
 
+        self.stopping.store(false, Ordering::SeqCst);
+
         // Clear any previous error
         *self.error_message.lock().unwrap() = None;
 
-        // Get sidecar name from environment variable (defaults to "mix")
-        let sidecar_name = env::var("SIDECAR_NAME")
-            .unwrap_or_else(|_| "mix".to_string());
-
         // Create sidecar command
-        let sidecar_command = match app.shell().sidecar(&sidecar_name) {
+        let sidecar_command = match app.shell().sidecar(&self.config.name) {
             Ok(cmd) => cmd,
             Err(e) => {
-                let error = format!("Failed to create sidecar command '{}': {}", sidecar_name, e);
+                let error = format!("Failed to create sidecar command '{}': {}", self.config.name, e);
                 *self.error_message.lock().unwrap() = Some(error.clone());
                 return Err(error);
             }
         };
 
-        println!("Starting sidecar '{}' with args: -c /Users/sarathmenon/Documents/startup/image_generation/mix --http-port 8088 --dangerously-skip-permissions -d", sidecar_name);
-        let command = sidecar_command.args(["-c", "/Users/sarathmenon/Documents/startup/image_generation/mix", "--http-port", "8088", "--dangerously-skip-permissions", "-d"]);
-        
+        let port_arg = self.config.port.to_string();
+        let mut args = vec![
+            "-c".to_string(),
+            self.config.config_dir.clone(),
+            "--http-port".to_string(),
+            port_arg,
+            "--dangerously-skip-permissions".to_string(),
+            "-d".to_string(),
+        ];
+        args.extend(self.config.extra_args.iter().cloned());
+
+        println!(
+            "Starting sidecar '{}' on port {} with args: {}",
+            self.config.name,
+            self.config.port,
+            args.join(" ")
+        );
+        let command = sidecar_command.args(args);
+
         match command.spawn() {
             Ok((mut rx, child)) => {
                 // Store the child process
                 *self.child.lock().unwrap() = Some(child);
 
-                // Simple monitoring task for logging
+                // Simple monitoring task for logging, plus supervised auto-restart and
+                // forwarding of live output to the frontend.
                 let error_message = Arc::clone(&self.error_message);
                 let child_ref = Arc::clone(&self.child);
+                let log_buffer = Arc::clone(&self.log_buffer);
+                let manager = Arc::clone(self);
+                let app_for_restart = app.clone();
+                let app_for_events = app.clone();
 
                 tokio::spawn(async move {
+                    let mut crashed = false;
                     while let Some(event) = rx.recv().await {
                         match event {
                             CommandEvent::Stdout(data) => {
-                                println!("Go server stdout: {}", String::from_utf8_lossy(&data));
+                                let line = String::from_utf8_lossy(&data).to_string();
+                                println!("Go server stdout: {}", line);
+                                push_log_line(&log_buffer, &app_for_events, "stdout", line);
                             }
                             CommandEvent::Stderr(data) => {
-                                println!("Go server stderr: {}", String::from_utf8_lossy(&data));
+                                let line = String::from_utf8_lossy(&data).to_string();
+                                println!("Go server stderr: {}", line);
+                                push_log_line(&log_buffer, &app_for_events, "stderr", line);
                             }
                             CommandEvent::Error(err) => {
                                 *error_message.lock().unwrap() = Some(format!("Process error: {}", err));
                                 *child_ref.lock().unwrap() = None;
+                                crashed = true;
+                                let _ = app_for_events.emit(
+                                    "sidecar://exit",
+                                    SidecarExit { code: None, error: Some(err) },
+                                );
                                 break;
                             }
                             CommandEvent::Terminated(payload) => {
                                 println!("Go server terminated with code: {:?}", payload.code);
                                 *child_ref.lock().unwrap() = None;
+                                let mut exit_error = None;
                                 if payload.code != Some(0) {
-                                    *error_message.lock().unwrap() = Some(format!(
+                                    let message = format!(
                                         "Process terminated with code: {:?}",
                                         payload.code
-                                    ));
+                                    );
+                                    if !manager.stopping.load(Ordering::SeqCst) {
+                                        *error_message.lock().unwrap() = Some(message.clone());
+                                        exit_error = Some(message);
+                                        crashed = true;
+                                    }
                                 }
+                                let _ = app_for_events.emit(
+                                    "sidecar://exit",
+                                    SidecarExit { code: payload.code, error: exit_error },
+                                );
                                 break;
                             }
                             _ => {}
                         }
                     }
+
+                    if crashed {
+                        manager.handle_unexpected_exit(app_for_restart).await;
+                    }
                 });
 
-                // Wait a moment for the server to start
-                sleep(Duration::from_millis(1000)).await;
-                Ok(())
+                // Wait for the server to actually come up before returning, instead of
+                // blindly sleeping and hoping for the best.
+                let ready = self.wait_until_ready().await;
+                if ready.is_ok() {
+                    self.spawn_stability_reset();
+                }
+                self.emit_status(app);
+                ready
             }
             Err(e) => {
                 let error = format!("Failed to spawn sidecar: {}", e);
                 *self.error_message.lock().unwrap() = Some(error.clone());
+                self.emit_status(app);
                 Err(error)
             }
         }
     }
 
-    pub async fn stop_sidecar(&self, _app: &AppHandle) -> Result<(), String> {
-        if let Some(child) = self.child.lock().unwrap().take() {
+    /// Poll the health endpoint until it reports healthy, the process dies, or the
+    /// overall deadline elapses. This replaces a fixed startup sleep so callers only
+    /// get `Ok(())` once the Go server can actually answer requests.
+    async fn wait_until_ready(&self) -> Result<(), String> {
+        let deadline = Instant::now() + READINESS_TIMEOUT;
+
+        loop {
+            if Instant::now() >= deadline {
+                let reason = self
+                    .get_error()
+                    .unwrap_or_else(|| "no response yet".to_string());
+                return Err(format!(
+                    "Timed out waiting for sidecar to become healthy after {:?}: {}",
+                    READINESS_TIMEOUT, reason
+                ));
+            }
+
+            // If the monitoring task already recorded a crash, stop waiting immediately
+            // instead of polling out the full deadline.
+            if let Some(error) = self.get_error() {
+                return Err(format!("Sidecar exited during startup: {}", error));
+            }
+
+            // A clean `Terminated(0)` during the readiness window clears the child
+            // slot without recording an error (it's not treated as a crash), so check
+            // for it separately -- otherwise we'd spin for the full timeout against a
+            // process that's already gone.
+            if self.child.lock().unwrap().is_none() {
+                return Err("Sidecar exited during startup (clean exit, code 0)".to_string());
+            }
+
+            if self.get_json::<HealthResponse>("/api/health").await.is_ok() {
+                return Ok(());
+            }
+
+            sleep(READINESS_POLL_INTERVAL).await;
+        }
+    }
+
+    pub async fn stop_sidecar(&self, app: &AppHandle) -> Result<(), String> {
+        // Mark this as a deliberate stop so the monitoring task doesn't treat the
+        // resulting `Terminated` event as a crash and try to restart it.
+        self.stopping.store(true, Ordering::SeqCst);
+
+        let result = if let Some(child) = self.child.lock().unwrap().take() {
             match child.kill() {
                 Ok(_) => {
                     println!("Sidecar process stopped successfully");
@@ -121,36 +411,120 @@ impl SidecarManager {
             }
         } else {
             Ok(()) // Already stopped
+        };
+
+        self.emit_status(app);
+        result
+    }
+
+    /// Called by the monitoring task when the child exits unexpectedly. Applies
+    /// exponential backoff and re-spawns the sidecar, unless a deliberate
+    /// `stop_sidecar` is in progress, the policy is disabled, or the crash-loop cap
+    /// for the current window has been hit.
+    async fn handle_unexpected_exit(self: Arc<Self>, app: AppHandle) {
+        self.emit_status(&app);
+
+        if !self.restart_policy.enabled || self.stopping.load(Ordering::SeqCst) {
+            return;
+        }
+
+        {
+            let mut window = self.restart_window.lock().unwrap();
+            let now = Instant::now();
+            if now.duration_since(window.0) > self.restart_policy.window {
+                *window = (now, 0);
+            }
+            window.1 += 1;
+            if window.1 > self.restart_policy.max_restarts_per_window {
+                *self.error_message.lock().unwrap() = Some(format!(
+                    "Sidecar crashed {} times within {:?}; giving up on auto-restart",
+                    window.1, self.restart_policy.window
+                ));
+                self.emit_status(&app);
+                return;
+            }
+        }
+
+        let attempt = self.restart_attempts.fetch_add(1, Ordering::SeqCst);
+        let delay = backoff_delay(&self.restart_policy, attempt);
+        println!(
+            "Sidecar crashed unexpectedly; restarting in {:?} (attempt {})",
+            delay,
+            attempt + 1
+        );
+        sleep(delay).await;
+
+        if self.stopping.load(Ordering::SeqCst) {
+            return;
+        }
+
+        if let Err(e) = self.start_sidecar(&app).await {
+            eprintln!("Automatic sidecar restart failed: {}", e);
         }
     }
 
-    pub async fn health_check(&self) -> Result<String, String> {
-        if self.child.lock().unwrap().is_none() {
-            return Err("Sidecar is not running".to_string());
-        }
-
-        match reqwest::get("http://localhost:8088/api/health").await {
-            Ok(response) => {
-                if response.status().is_success() {
-                    match response.json::<serde_json::Value>().await {
-                        Ok(data) => {
-                            if let Some(status) = data.get("status").and_then(|s| s.as_str()) {
-                                Ok(format!("Mix health check: {}", status))
-                            } else {
-                                Ok("Mix health check successful".to_string())
-                            }
-                        }
-                        Err(e) => Err(format!("Failed to parse response: {}", e)),
-                    }
-                } else {
-                    Err(format!(
-                        "Health check failed with status: {}",
-                        response.status()
-                    ))
-                }
+    /// Once the sidecar has been healthy for `stable_after`, reset the backoff
+    /// counter so a later, unrelated crash doesn't inherit a long delay.
+    fn spawn_stability_reset(self: &Arc<Self>) {
+        let manager = Arc::clone(self);
+        let stable_after = self.restart_policy.stable_after;
+        tokio::spawn(async move {
+            sleep(stable_after).await;
+            if manager.health_check().await.is_ok() {
+                manager.restart_attempts.store(0, Ordering::SeqCst);
             }
-            Err(e) => Err(format!("Health check request failed: {}", e)),
+        });
+    }
+
+    /// Centralizes base-URL construction, the "is anything even running" check,
+    /// status-code handling, and transport errors for every `POST` sidecar endpoint.
+    /// `send_prompt_streaming_with_sink` builds on this and reads the response body
+    /// incrementally rather than deserializing it whole.
+    async fn post<Req: Serialize>(&self, path: &str, body: &Req) -> Result<reqwest::Response, SidecarError> {
+        if self.child.lock().unwrap().is_none() {
+            return Err(SidecarError::NotRunning);
         }
+
+        let client = reqwest::Client::new();
+        let response = client
+            .post(format!("{}{}", base_url(self.config.port), path))
+            .json(body)
+            .send()
+            .await
+            .map_err(SidecarError::Transport)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(SidecarError::Http(status));
+        }
+
+        Ok(response)
+    }
+
+    /// Same contract as `post`, plus JSON deserialization of the response, for
+    /// endpoints that take a plain `GET` with no body.
+    async fn get_json<Resp: DeserializeOwned>(&self, path: &str) -> Result<Resp, SidecarError> {
+        if self.child.lock().unwrap().is_none() {
+            return Err(SidecarError::NotRunning);
+        }
+
+        let response = reqwest::get(format!("{}{}", base_url(self.config.port), path))
+            .await
+            .map_err(SidecarError::Transport)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(SidecarError::Http(status));
+        }
+
+        response.json::<Resp>().await.map_err(SidecarError::Decode)
+    }
+
+    pub async fn health_check(&self) -> Result<String, String> {
+        self.get_json::<HealthResponse>("/api/health")
+            .await
+            .map(|health| format!("Mix health check: {}", health.status))
+            .map_err(String::from)
     }
 
     pub fn is_running(&self) -> bool {
@@ -161,33 +535,188 @@ impl SidecarManager {
         self.error_message.lock().unwrap().clone()
     }
 
-    pub async fn send_prompt(&self, prompt: &str) -> Result<String, String> {
-        if self.child.lock().unwrap().is_none() {
-            return Err("Sidecar is not running".to_string());
-        }
+    /// Broadcasts the current running/stopped/error state on `"sidecar://status"` so
+    /// listeners (e.g. the tray menu) can refresh without polling.
+    fn emit_status(&self, app: &AppHandle) {
+        let _ = app.emit(
+            "sidecar://status",
+            SidecarStatus {
+                name: self.config.name.clone(),
+                running: self.is_running(),
+                error: self.get_error(),
+            },
+        );
+    }
 
-        let client = reqwest::Client::new();
-        let payload = serde_json::json!({
-            "prompt": prompt
-        });
+    /// Pushes bytes to the child's stdin, for driving an interactive/REPL-style
+    /// session instead of one-shot HTTP calls. Paired with the stdout/stderr event
+    /// forwarding above, this gives a frontend terminal a true bidirectional session.
+    pub async fn write_stdin(&self, data: &[u8]) -> Result<(), String> {
+        let guard = self.child.lock().unwrap();
+        let child = guard
+            .as_ref()
+            .ok_or_else(|| "Sidecar is not running".to_string())?;
 
-        match client
-            .post("http://localhost:8088/api/prompt")
-            .json(&payload)
-            .send()
-            .await
-        {
-            Ok(response) => {
-                if response.status().is_success() {
-                    match response.text().await {
-                        Ok(text) => Ok(text),
-                        Err(e) => Err(format!("Failed to read response: {}", e)),
+        child.write(data).map_err(|e| {
+            let error = format!("Failed to write to sidecar stdin: {}", e);
+            *self.error_message.lock().unwrap() = Some(error.clone());
+            error
+        })
+    }
+
+    /// Recent captured stdout/stderr lines, oldest first, for a newly opened window
+    /// to backfill its log view with.
+    pub fn recent_logs(&self) -> Vec<SidecarLogLine> {
+        self.log_buffer.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Convenience wrapper over `send_prompt_streaming` for callers that just want the
+    /// whole response as one `String` and don't care about incremental delivery. Still
+    /// streams under the hood, using an internally generated `request_id` that isn't
+    /// exposed for cancellation.
+    pub async fn send_prompt(&self, app: &AppHandle, prompt: &str) -> Result<String, String> {
+        let request_id = format!("internal-{}", next_prompt_id());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+        let streaming = self.send_prompt_streaming_with_sink(app, prompt, &request_id, Some(tx));
+        let accumulate = async {
+            let mut response = String::new();
+            while let Some(delta) = rx.recv().await {
+                response.push_str(&delta);
+            }
+            response
+        };
+        let (result, response) = tokio::join!(streaming, accumulate);
+        result.map(|()| response)
+    }
+
+    /// Reads the sidecar's response incrementally and emits `"sidecar-stream-chunk"`
+    /// events (`{ request_id, delta }`) as tokens arrive, followed by a terminal
+    /// `"sidecar-stream-done"` (or `"sidecar-stream-error"` on failure). Cancel an
+    /// in-flight call with `cancel_prompt(request_id)`.
+    pub async fn send_prompt_streaming(
+        &self,
+        app: &AppHandle,
+        prompt: &str,
+        request_id: &str,
+    ) -> Result<(), String> {
+        self.send_prompt_streaming_with_sink(app, prompt, request_id, None).await
+    }
+
+    /// Shared implementation behind `send_prompt` and `send_prompt_streaming`. `sink`
+    /// additionally forwards each delta to an accumulator when `send_prompt` is
+    /// driving this call.
+    async fn send_prompt_streaming_with_sink(
+        &self,
+        app: &AppHandle,
+        prompt: &str,
+        request_id: &str,
+        sink: Option<tokio::sync::mpsc::UnboundedSender<String>>,
+    ) -> Result<(), String> {
+        // `send_prompt` drives this with `sink: Some(..)` and an internally generated
+        // `request_id` it never exposes; the `sidecar-stream-*` events are for genuine
+        // `send_prompt_streaming` callers only, so skip emitting them here -- otherwise
+        // every plain, non-streaming `send_prompt` call would also spam listeners with
+        // spurious chunk/done/error traffic for a request they never asked about.
+        let is_streaming_call = sink.is_none();
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.active_prompts
+            .lock()
+            .unwrap()
+            .insert(request_id.to_string(), cancelled.clone());
+        let _guard = PromptGuard {
+            active_prompts: &self.active_prompts,
+            request_id,
+        };
+
+        let response = match self.post("/api/prompt", &PromptRequest { prompt }).await {
+            Ok(response) => response,
+            Err(e) => {
+                let error = String::from(e);
+                if is_streaming_call {
+                    let _ = app.emit("sidecar-stream-error", PromptError { request_id: request_id.to_string(), error: error.clone() });
+                }
+                return Err(error);
+            }
+        };
+
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            if cancelled.load(Ordering::SeqCst) {
+                let error = "Cancelled".to_string();
+                if is_streaming_call {
+                    let _ = app.emit("sidecar-stream-error", PromptError { request_id: request_id.to_string(), error: error.clone() });
+                }
+                return Err(error);
+            }
+
+            match chunk {
+                Ok(bytes) => {
+                    let delta = String::from_utf8_lossy(&bytes).to_string();
+                    if let Some(sink) = &sink {
+                        let _ = sink.send(delta.clone());
+                    }
+                    if is_streaming_call {
+                        let _ = app.emit(
+                            "sidecar-stream-chunk",
+                            PromptChunk { request_id: request_id.to_string(), delta },
+                        );
                     }
-                } else {
-                    Err(format!("Request failed with status: {}", response.status()))
+                }
+                Err(e) => {
+                    let error = format!("Failed to read response chunk: {}", e);
+                    if is_streaming_call {
+                        let _ = app.emit("sidecar-stream-error", PromptError { request_id: request_id.to_string(), error: error.clone() });
+                    }
+                    return Err(error);
                 }
             }
-            Err(e) => Err(format!("Request failed: {}", e)),
         }
+
+        if is_streaming_call {
+            let _ = app.emit("sidecar-stream-done", PromptDone { request_id: request_id.to_string() });
+        }
+        Ok(())
+    }
+
+    /// Aborts the in-flight `send_prompt_streaming` call for `request_id`, if any.
+    /// The cancellation is observed when the next chunk arrives, which then emits
+    /// `"sidecar-stream-error"` instead of running to completion; a call waiting on a
+    /// chunk is not interrupted, and a response that ends without another chunk
+    /// completes normally with `"sidecar-stream-done"`.
+    pub fn cancel_prompt(&self, request_id: &str) {
+        if let Some(cancelled) = self.active_prompts.lock().unwrap().get(request_id) {
+            cancelled.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Removes a request's cancellation flag from `active_prompts` once its streaming
+/// call returns, win or lose, so the map doesn't grow unbounded.
+struct PromptGuard<'a> {
+    active_prompts: &'a Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+    request_id: &'a str,
+}
+
+impl Drop for PromptGuard<'_> {
+    fn drop(&mut self) {
+        self.active_prompts.lock().unwrap().remove(self.request_id);
     }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct PromptChunk {
+    request_id: String,
+    delta: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct PromptDone {
+    request_id: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct PromptError {
+    request_id: String,
+    error: String,
 }
\ No newline at end of file